@@ -0,0 +1,87 @@
+//! Classifying a [`Link`] by the kind of resource it points at, so it can
+//! be routed to the [`Verifier`](super::Verifier)s registered for that
+//! kind instead of being probed against every verifier in turn.
+
+use crate::Link;
+
+/// The broad category a [`Link`] falls into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkType {
+    /// A path to something on the local filesystem.
+    FileSystem,
+    /// An `http://` or `https://` URL.
+    Http,
+    /// A `mailto:` address.
+    Mailto,
+    /// An `ftp://` or `ftps://` URL.
+    Ftp,
+    /// A `tel:` number.
+    Tel,
+}
+
+/// Work out which [`LinkType`] a [`Link`] belongs to, returning `None` if
+/// it uses a scheme we don't recognise at all.
+pub fn classify(link: &Link) -> Option<LinkType> {
+    let href = link.href();
+
+    if let Some((candidate, _)) = href.split_once(':') {
+        if is_uri_scheme(candidate) {
+            return match candidate {
+                "http" | "https" => Some(LinkType::Http),
+                "mailto" => Some(LinkType::Mailto),
+                "ftp" | "ftps" => Some(LinkType::Ftp),
+                "tel" => Some(LinkType::Tel),
+                _ => None,
+            };
+        }
+    }
+
+    Some(LinkType::FileSystem)
+}
+
+/// Does `candidate` look like an RFC 3986 URI scheme, rather than a
+/// Windows drive letter (`C:\...`) or a colon that just happens to appear
+/// somewhere in a relative path or fragment (`./notes/2024:summary.md`,
+/// `./guide.md#time:12:30`)?
+///
+/// A real scheme is a letter followed by one or more letters, digits,
+/// `+`, `-`, or `.`; in particular it never contains a `/`, and a bare
+/// drive letter (length 1) doesn't count.
+fn is_uri_scheme(candidate: &str) -> bool {
+    let mut chars = candidate.chars();
+
+    matches!(chars.next(), Some(first) if first.is_ascii_alphabetic())
+        && chars.next().is_some()
+        && candidate
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognised_schemes_count_as_uri_schemes() {
+        assert!(is_uri_scheme("http"));
+        assert!(is_uri_scheme("https"));
+        assert!(is_uri_scheme("mailto"));
+        assert!(is_uri_scheme("ftp"));
+        assert!(is_uri_scheme("tel"));
+    }
+
+    #[test]
+    fn drive_letters_are_not_uri_schemes() {
+        assert!(!is_uri_scheme("C"));
+        assert!(!is_uri_scheme("D"));
+    }
+
+    #[test]
+    fn relative_paths_with_colons_are_not_uri_schemes() {
+        // `./notes/2024:summary.md`: the candidate before the `:` contains
+        // a `/`, so it can't be a scheme.
+        assert!(!is_uri_scheme("./notes/2024"));
+        // `./guide.md#time:12:30`: ditto, for a colon inside a fragment.
+        assert!(!is_uri_scheme("./guide.md#time"));
+    }
+}
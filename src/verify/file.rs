@@ -0,0 +1,74 @@
+//! Verifying links which point at a file on disk.
+
+use super::{fragment, ValidationResult, Verifier};
+use crate::{Cache, Link};
+use std::{
+    fs,
+    io,
+    path::PathBuf,
+};
+
+/// A [`Verifier`] for checking links which point at a file on disk.
+///
+/// Relative links are resolved against `root`.
+#[derive(Debug, Clone)]
+pub struct File {
+    root: PathBuf,
+}
+
+impl File {
+    /// Create a new [`File`] verifier which resolves relative links
+    /// against `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        File { root: root.into() }
+    }
+}
+
+impl Verifier for File {
+    fn verify(&self, link: &Link, _cache: &dyn Cache) -> ValidationResult {
+        let (path, fragment) = split_fragment(link.href());
+
+        if is_remote(path) {
+            return ValidationResult::Unsupported;
+        }
+
+        let full_path = self.root.join(path);
+
+        let contents = match fs::read_to_string(&full_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return ValidationResult::Invalid {
+                    reason: Box::new(e),
+                };
+            }
+            Err(_) => {
+                // Not a UTF-8 text file (e.g. an image or a PDF), so the
+                // best we can do is check that it exists.
+                return if full_path.exists() {
+                    ValidationResult::Valid
+                } else {
+                    ValidationResult::Unsupported
+                };
+            }
+        };
+
+        if let Some(fragment) = fragment {
+            if !fragment::document_has_anchor(&full_path, &contents, fragment) {
+                return ValidationResult::Invalid {
+                    reason: fragment::missing_anchor_error(fragment),
+                };
+            }
+        }
+
+        ValidationResult::Valid
+    }
+}
+
+fn is_remote(path: &str) -> bool { path.contains("://") }
+
+fn split_fragment(href: &str) -> (&str, Option<&str>) {
+    match href.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (href, None),
+    }
+}
@@ -0,0 +1,188 @@
+//! Anchor/fragment resolution shared by the [`File`](super::File) and
+//! [`Web`](super::Web) verifiers.
+
+use std::{collections::HashMap, error::Error, fmt, path::Path};
+
+/// Does `contents` contain an element whose `id`/`name` (or, for Markdown, a
+/// GitHub-style slugified heading) matches `fragment`?
+pub(crate) fn document_has_anchor(path: &Path, contents: &str, fragment: &str) -> bool {
+    if is_markdown(path) {
+        markdown_has_anchor(contents, fragment)
+    } else {
+        html_has_anchor(contents, fragment)
+    }
+}
+
+/// Does this HTML document contain an element with a matching `id` or
+/// `name` attribute?
+pub(crate) fn html_has_anchor(contents: &str, fragment: &str) -> bool {
+    html_anchors(contents).any(|id| id == fragment)
+}
+
+/// Does this Markdown document contain a heading which slugifies to
+/// `fragment`?
+pub(crate) fn markdown_has_anchor(contents: &str, fragment: &str) -> bool {
+    markdown_slugs(contents).any(|slug| slug == fragment)
+}
+
+/// Construct the [`Error`] returned when a document exists but doesn't
+/// contain the requested anchor.
+pub(crate) fn missing_anchor_error(fragment: &str) -> Box<dyn Error + Send + Sync> {
+    Box::new(MissingAnchor {
+        fragment: fragment.to_string(),
+    })
+}
+
+#[derive(Debug)]
+struct MissingAnchor {
+    fragment: String,
+}
+
+impl fmt::Display for MissingAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no element with id or name \"{}\"", self.fragment)
+    }
+}
+
+impl Error for MissingAnchor {}
+
+fn is_markdown(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("md") | Some("markdown")
+    )
+}
+
+/// Extract every `id="..."` / `name="..."` attribute value (single- or
+/// double-quoted) from a blob of HTML.
+fn html_anchors(contents: &str) -> impl Iterator<Item = &str> {
+    const NEEDLES: [(&str, char); 4] = [
+        ("id=\"", '"'),
+        ("id='", '\''),
+        ("name=\"", '"'),
+        ("name='", '\''),
+    ];
+
+    NEEDLES.iter().flat_map(move |(needle, quote)| {
+        contents
+            .match_indices(needle)
+            .filter(|(start, _)| is_attribute_boundary(contents, *start))
+            .filter_map(move |(start, _)| {
+                let rest = &contents[start + needle.len()..];
+                rest.find(*quote).map(|end| &rest[..end])
+            })
+    })
+}
+
+/// Is the byte at `start` the beginning of an attribute name, rather than
+/// the tail of a longer one (e.g. the `id` in `data-id="..."` or
+/// `aria-id="..."`)?
+fn is_attribute_boundary(contents: &str, start: usize) -> bool {
+    match contents[..start].chars().next_back() {
+        None => true,
+        Some(c) => c.is_whitespace() || c == '"' || c == '\'' || c == '<',
+    }
+}
+
+/// Slugify every heading in a Markdown document using the same algorithm
+/// GitHub uses, deduplicating repeated slugs by appending `-1`, `-2`, etc.
+fn markdown_slugs(contents: &str) -> impl Iterator<Item = String> + '_ {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    contents
+        .lines()
+        .map(str::trim_start)
+        .filter(|line| line.starts_with('#'))
+        .map(|line| line.trim_start_matches('#').trim())
+        .map(move |heading| {
+            let base = slugify(heading);
+            let count = seen.entry(base.clone()).or_insert(0);
+            let slug = if *count == 0 {
+                base
+            } else {
+                format!("{}-{}", base, count)
+            };
+            *count += 1;
+            slug
+        })
+}
+
+/// Lowercase the heading, strip anything that isn't alphanumeric,
+/// whitespace, or a hyphen, then collapse runs of whitespace into single
+/// hyphens.
+fn slugify(heading: &str) -> String {
+    let cleaned: String = heading
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '-')
+        .collect();
+
+    cleaned.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_lowercases_and_hyphenates() {
+        assert_eq!(slugify("Installation Guide"), "installation-guide");
+        assert_eq!(slugify("  Extra   Spaces  "), "extra-spaces");
+        assert_eq!(slugify("What's New?!"), "whats-new");
+    }
+
+    #[test]
+    fn markdown_slugs_dedup_with_numeric_suffixes() {
+        let contents = "# Overview\n\n## Overview\n\n### Overview\n";
+        let slugs: Vec<_> = markdown_slugs(contents).collect();
+        assert_eq!(slugs, vec!["overview", "overview-1", "overview-2"]);
+    }
+
+    #[test]
+    fn html_anchors_only_matches_whole_attribute_names() {
+        let contents = r#"<div data-id="not-an-anchor">
+            <h2 id="installation">Installation</h2>
+            <a name='legacy-anchor'></a>
+        </div>"#;
+
+        let anchors: Vec<_> = html_anchors(contents).collect();
+
+        assert!(anchors.contains(&"installation"));
+        assert!(anchors.contains(&"legacy-anchor"));
+        assert!(!anchors.contains(&"not-an-anchor"));
+    }
+
+    #[test]
+    fn html_anchors_handles_single_quoted_attributes() {
+        assert!(html_has_anchor(
+            "<a name='legacy-anchor'>link</a>",
+            "legacy-anchor"
+        ));
+        assert!(html_has_anchor(
+            "<h2 id='installation'>Installation</h2>",
+            "installation"
+        ));
+    }
+
+    #[test]
+    fn document_has_anchor_dispatches_on_extension() {
+        let markdown = "# Installation\n";
+        assert!(document_has_anchor(
+            Path::new("guide.md"),
+            markdown,
+            "installation"
+        ));
+
+        let html = r#"<h2 id="installation">Installation</h2>"#;
+        assert!(document_has_anchor(
+            Path::new("guide.html"),
+            html,
+            "installation"
+        ));
+        assert!(!document_has_anchor(
+            Path::new("guide.html"),
+            markdown,
+            "installation"
+        ));
+    }
+}
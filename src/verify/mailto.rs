@@ -0,0 +1,150 @@
+//! Verifying `mailto:` links.
+
+use super::{ValidationResult, Verifier};
+use crate::{Cache, Link};
+use std::{error::Error, fmt};
+
+/// A [`Verifier`] for `mailto:` links.
+///
+/// By default this only checks that the address is syntactically valid.
+/// Enable [`Mailto::check_mx_record`] to additionally confirm the domain
+/// resolves to an MX record, at the cost of a DNS lookup per address.
+#[derive(Debug, Clone, Default)]
+pub struct Mailto {
+    check_mx_record: bool,
+}
+
+impl Mailto {
+    /// Create a new [`Mailto`] verifier which only checks syntax.
+    pub fn new() -> Self { Mailto::default() }
+
+    /// Also confirm the address' domain has an MX record.
+    pub fn check_mx_record(mut self, check: bool) -> Self {
+        self.check_mx_record = check;
+        self
+    }
+}
+
+impl Verifier for Mailto {
+    fn verify(&self, link: &Link, _cache: &dyn Cache) -> ValidationResult {
+        let address = match link.href().strip_prefix("mailto:") {
+            Some(address) => address.split('?').next().unwrap_or_default(),
+            None => return ValidationResult::Unsupported,
+        };
+
+        let domain = match parse_address(address) {
+            Some(domain) => domain,
+            None => {
+                return ValidationResult::Invalid {
+                    reason: Box::new(InvalidAddress),
+                }
+            }
+        };
+
+        if self.check_mx_record && !has_mx_record(domain) {
+            return ValidationResult::Invalid {
+                reason: Box::new(NoMxRecord {
+                    domain: domain.to_string(),
+                }),
+            };
+        }
+
+        ValidationResult::Valid
+    }
+}
+
+/// A deliberately conservative syntactic check for an RFC 5322 mailbox of
+/// the form `local-part@domain`, returning the domain on success.
+fn parse_address(address: &str) -> Option<&str> {
+    let (local, domain) = address.split_once('@')?;
+
+    let valid_local = !local.is_empty()
+        && local
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(is_atext));
+    let valid_domain = !domain.is_empty()
+        && domain
+            .split('.')
+            .all(|label| !label.is_empty() && label.chars().all(is_domain_char));
+
+    if valid_local && valid_domain {
+        Some(domain)
+    } else {
+        None
+    }
+}
+
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+fn is_domain_char(c: char) -> bool { c.is_ascii_alphanumeric() || c == '-' }
+
+fn has_mx_record(domain: &str) -> bool {
+    use trust_dns_resolver::{config::*, Resolver};
+
+    Resolver::new(ResolverConfig::default(), ResolverOpts::default())
+        .and_then(|resolver| resolver.mx_lookup(domain))
+        .map(|lookup| lookup.iter().next().is_some())
+        .unwrap_or(false)
+}
+
+#[derive(Debug)]
+struct InvalidAddress;
+
+impl fmt::Display for InvalidAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not a valid email address")
+    }
+}
+
+impl Error for InvalidAddress {}
+
+#[derive(Debug)]
+struct NoMxRecord {
+    domain: String,
+}
+
+impl fmt::Display for NoMxRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" has no MX record", self.domain)
+    }
+}
+
+impl Error for NoMxRecord {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_plain_addresses() {
+        assert_eq!(parse_address("jane@example.com"), Some("example.com"));
+    }
+
+    #[test]
+    fn accepts_dotted_local_parts_and_subdomains() {
+        assert_eq!(
+            parse_address("first.last@mail.example.co.uk"),
+            Some("mail.example.co.uk")
+        );
+    }
+
+    #[test]
+    fn rejects_addresses_without_an_at_sign() {
+        assert_eq!(parse_address("not-an-address"), None);
+    }
+
+    #[test]
+    fn rejects_empty_local_or_domain_parts() {
+        assert_eq!(parse_address("@example.com"), None);
+        assert_eq!(parse_address("jane@"), None);
+        assert_eq!(parse_address("jane@example..com"), None);
+    }
+
+    #[test]
+    fn rejects_domains_with_invalid_characters() {
+        assert_eq!(parse_address("jane@exa mple.com"), None);
+        assert_eq!(parse_address("jane@example.com/"), None);
+    }
+}
@@ -1,13 +1,20 @@
 //! Link verification.
 
+mod classify;
 mod file;
+mod fragment;
+mod mailto;
+mod report;
 mod web;
 
+pub use classify::{classify, LinkType};
 pub use file::File;
+pub use mailto::Mailto;
 pub use web::Web;
 
 use crate::{Cache, Link, Location};
 use rayon::{iter::ParallelBridge, prelude::*};
+use std::{collections::HashMap, error::Error};
 
 /// Something used to check whether a link is valid.
 pub trait Verifier: Sync {
@@ -31,6 +38,39 @@ where
     }
 }
 
+/// A registry mapping each [`LinkType`] to the [`Verifier`]s which know how
+/// to check it.
+///
+/// Rather than probing every [`Verifier`] in turn, [`verify()`] uses
+/// [`classify()`] to work out a [`Link`]'s [`LinkType`] up front and only
+/// tries the verifiers registered for that type.
+#[derive(Default)]
+pub struct Verifiers {
+    by_type: HashMap<LinkType, Vec<Box<dyn Verifier>>>,
+}
+
+impl Verifiers {
+    /// Create an empty registry.
+    pub fn new() -> Self { Verifiers::default() }
+
+    /// Register a [`Verifier`] to handle links of a particular [`LinkType`].
+    pub fn register(
+        mut self,
+        link_type: LinkType,
+        verifier: impl Verifier + 'static,
+    ) -> Self {
+        self.by_type
+            .entry(link_type)
+            .or_default()
+            .push(Box::new(verifier));
+        self
+    }
+
+    fn verifiers_for(&self, link_type: LinkType) -> Option<&[Box<dyn Verifier>]> {
+        self.by_type.get(&link_type).map(Vec::as_slice)
+    }
+}
+
 #[derive(Debug)]
 pub enum ValidationResult {
     /// This [`Link`] is valid.
@@ -39,21 +79,85 @@ pub enum ValidationResult {
     Unsupported,
     /// The link should be ignored.
     Ignored,
+    /// The [`Link`] is invalid, together with why it failed.
+    Invalid {
+        /// Why wasn't this link considered valid?
+        reason: Box<dyn Error + Send + Sync>,
+    },
 }
 
+/// The result of checking a batch of links.
+///
+/// An [`Outcome`] accumulates every link encountered during a [`verify()`]
+/// run, bucketed by whether it resolved, failed, or was skipped.
 #[derive(Debug, Default)]
-pub struct Outcome {}
+pub struct Outcome {
+    valid_links: Vec<(Location, Link)>,
+    invalid_links: Vec<(Location, Link, Box<dyn Error + Send + Sync>)>,
+    ignored_links: Vec<(Location, Link)>,
+}
 
 impl Outcome {
-    pub fn merge(left: Outcome, right: Outcome) -> Outcome { unimplemented!() }
+    /// Combine two partial [`Outcome`]s into one.
+    pub fn merge(mut left: Outcome, mut right: Outcome) -> Outcome {
+        left.valid_links.append(&mut right.valid_links);
+        left.invalid_links.append(&mut right.invalid_links);
+        left.ignored_links.append(&mut right.ignored_links);
+        left
+    }
 
+    /// Record the result of checking a single [`Link`].
     fn with_result(
-        self,
+        mut self,
         location: Location,
         link: Link,
         result: ValidationResult,
     ) -> Outcome {
-        unimplemented!()
+        match result {
+            ValidationResult::Valid => self.valid_links.push((location, link)),
+            ValidationResult::Invalid { reason } => {
+                self.invalid_links.push((location, link, reason))
+            }
+            ValidationResult::Unsupported | ValidationResult::Ignored => {
+                self.ignored_links.push((location, link))
+            }
+        }
+
+        self
+    }
+
+    /// The links which resolved successfully.
+    pub fn valid_links(&self) -> &[(Location, Link)] { &self.valid_links }
+
+    /// The links which failed validation, together with the reason why.
+    pub fn invalid_links(
+        &self,
+    ) -> &[(Location, Link, Box<dyn Error + Send + Sync>)] {
+        &self.invalid_links
+    }
+
+    /// Links which were skipped, either because their type isn't supported
+    /// or because they were explicitly ignored.
+    pub fn ignored_links(&self) -> &[(Location, Link)] { &self.ignored_links }
+
+    /// Were there no links at all, valid, invalid, or ignored?
+    pub fn is_empty(&self) -> bool {
+        self.valid_links.is_empty()
+            && self.invalid_links.is_empty()
+            && self.ignored_links.is_empty()
+    }
+
+    /// Did every link either resolve or get skipped, i.e. did nothing fail?
+    pub fn succeeded(&self) -> bool { self.invalid_links.is_empty() }
+
+    /// A process exit code summarising the run: `0` if every link was valid
+    /// or ignored, `1` if at least one link was invalid.
+    pub fn exit_code(&self) -> i32 {
+        if self.succeeded() {
+            0
+        } else {
+            1
+        }
     }
 }
 
@@ -71,11 +175,7 @@ impl FromParallelIterator<(Location, Link, ValidationResult)> for Outcome {
     }
 }
 
-pub fn verify<L, C>(
-    links: L,
-    verifiers: &[Box<dyn Verifier>],
-    cache: &dyn Cache,
-) -> Outcome
+pub fn verify<L, C>(links: L, verifiers: &Verifiers, cache: &dyn Cache) -> Outcome
 where
     L: IntoIterator<Item = (Location, Link)>,
     L::IntoIter: Send,
@@ -91,22 +191,150 @@ where
         .collect()
 }
 
-fn verify_one(
-    link: &Link,
-    verifiers: &[Box<dyn Verifier>],
-    cache: &dyn Cache,
-) -> ValidationResult {
+fn verify_one(link: &Link, verifiers: &Verifiers, cache: &dyn Cache) -> ValidationResult {
     if cache.is_valid(link.href()).unwrap_or(false) {
         // cache hit
         return ValidationResult::Valid;
     }
 
-    for verifier in verifiers {
+    let link_type = match classify(link) {
+        Some(link_type) => link_type,
+        None => return ValidationResult::Ignored,
+    };
+
+    let registered = match verifiers.verifiers_for(link_type) {
+        Some(registered) => registered,
+        None => return ValidationResult::Ignored,
+    };
+
+    for verifier in registered {
         match verifier.verify(link, cache) {
             ValidationResult::Unsupported => continue,
             other => return other,
         }
     }
 
-    ValidationResult::Unsupported
+    ValidationResult::Ignored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    struct NoCache;
+
+    impl Cache for NoCache {
+        fn is_valid(&self, _href: &str) -> Option<bool> { None }
+    }
+
+    fn location() -> Location { Location::new("docs/guide.md", 4, 2) }
+
+    fn not_found() -> Box<dyn Error + Send + Sync> {
+        Box::new(io::Error::new(io::ErrorKind::NotFound, "nope"))
+    }
+
+    #[test]
+    fn with_result_routes_valid_invalid_and_ignored_into_their_own_buckets() {
+        let outcome = Outcome::default()
+            .with_result(location(), Link::new("./guide.md"), ValidationResult::Valid)
+            .with_result(
+                location(),
+                Link::new("./missing.md"),
+                ValidationResult::Invalid { reason: not_found() },
+            )
+            .with_result(location(), Link::new("tel:123"), ValidationResult::Ignored)
+            .with_result(
+                location(),
+                Link::new("xmpp:user@example.com"),
+                ValidationResult::Unsupported,
+            );
+
+        assert_eq!(outcome.valid_links().len(), 1);
+        assert_eq!(outcome.invalid_links().len(), 1);
+        // Both `Unsupported` and `Ignored` land in the same ignored bucket.
+        assert_eq!(outcome.ignored_links().len(), 2);
+    }
+
+    #[test]
+    fn merge_concatenates_every_bucket() {
+        let left =
+            Outcome::default().with_result(location(), Link::new("a"), ValidationResult::Valid);
+        let right = Outcome::default().with_result(
+            location(),
+            Link::new("b"),
+            ValidationResult::Invalid { reason: not_found() },
+        );
+
+        let merged = Outcome::merge(left, right);
+
+        assert_eq!(merged.valid_links().len(), 1);
+        assert_eq!(merged.invalid_links().len(), 1);
+    }
+
+    #[test]
+    fn is_empty_requires_every_bucket_to_be_empty() {
+        assert!(Outcome::default().is_empty());
+
+        let outcome =
+            Outcome::default().with_result(location(), Link::new("a"), ValidationResult::Ignored);
+        assert!(!outcome.is_empty());
+    }
+
+    #[test]
+    fn succeeded_and_exit_code_only_care_about_invalid_links() {
+        let ok =
+            Outcome::default().with_result(location(), Link::new("a"), ValidationResult::Valid);
+        assert!(ok.succeeded());
+        assert_eq!(ok.exit_code(), 0);
+
+        let failed = Outcome::default().with_result(
+            location(),
+            Link::new("b"),
+            ValidationResult::Invalid { reason: not_found() },
+        );
+        assert!(!failed.succeeded());
+        assert_eq!(failed.exit_code(), 1);
+    }
+
+    #[test]
+    fn verify_one_short_circuits_on_a_cache_hit_without_running_any_verifier() {
+        struct AlwaysCached;
+
+        impl Cache for AlwaysCached {
+            fn is_valid(&self, _href: &str) -> Option<bool> { Some(true) }
+        }
+
+        let verifiers = Verifiers::new().register(LinkType::Http, |_: &Link, _: &dyn Cache| {
+            panic!("verifier should never run on a cache hit")
+        });
+
+        let result = verify_one(&Link::new("https://example.com"), &verifiers, &AlwaysCached);
+
+        assert!(matches!(result, ValidationResult::Valid));
+    }
+
+    #[test]
+    fn unregistered_link_types_resolve_to_ignored_not_unsupported() {
+        let verifiers = Verifiers::new()
+            .register(LinkType::Http, |_: &Link, _: &dyn Cache| ValidationResult::Valid);
+
+        // `tel:` has no registered verifier.
+        let result = verify_one(&Link::new("tel:123-456"), &verifiers, &NoCache);
+
+        assert!(matches!(result, ValidationResult::Ignored));
+    }
+
+    #[test]
+    fn unsupported_falls_through_to_the_next_registered_verifier() {
+        let verifiers = Verifiers::new()
+            .register(LinkType::Http, |_: &Link, _: &dyn Cache| {
+                ValidationResult::Unsupported
+            })
+            .register(LinkType::Http, |_: &Link, _: &dyn Cache| ValidationResult::Valid);
+
+        let result = verify_one(&Link::new("https://example.com"), &verifiers, &NoCache);
+
+        assert!(matches!(result, ValidationResult::Valid));
+    }
 }
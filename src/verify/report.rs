@@ -0,0 +1,255 @@
+//! Turning an [`Outcome`] into machine-readable reports that CI systems can
+//! consume directly, instead of scraping human-readable text.
+
+use super::Outcome;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+impl Outcome {
+    /// Serialize this [`Outcome`] using the crate's stable JSON report
+    /// schema: total/valid/invalid/ignored counts, plus one entry per
+    /// invalid link giving its `href`, originating location, and the
+    /// reason it failed.
+    pub fn to_json(&self) -> Value {
+        let failures: Vec<_> = self
+            .invalid_links()
+            .iter()
+            .map(|(location, link, reason)| {
+                json!({
+                    "href": link.href(),
+                    "path": location.path().display().to_string(),
+                    "line": location.line(),
+                    "column": location.column(),
+                    "reason": reason.to_string(),
+                })
+            })
+            .collect();
+
+        json!({
+            "total": self.valid_links().len()
+                + self.invalid_links().len()
+                + self.ignored_links().len(),
+            "valid": self.valid_links().len(),
+            "invalid": self.invalid_links().len(),
+            "ignored": self.ignored_links().len(),
+            "failures": failures,
+        })
+    }
+
+    /// Render this [`Outcome`] as a JUnit XML report: one `<testsuite>` per
+    /// checked file and one `<testcase>` per link, with a `<failure>`
+    /// element for links which didn't validate.
+    pub fn to_junit(&self) -> String {
+        let mut suites: BTreeMap<String, Vec<TestCase>> = BTreeMap::new();
+
+        for (location, link) in self.valid_links() {
+            suites
+                .entry(location.path().display().to_string())
+                .or_default()
+                .push(TestCase {
+                    name: link.href().to_string(),
+                    outcome: CaseOutcome::Pass,
+                });
+        }
+
+        for (location, link, reason) in self.invalid_links() {
+            suites
+                .entry(location.path().display().to_string())
+                .or_default()
+                .push(TestCase {
+                    name: link.href().to_string(),
+                    outcome: CaseOutcome::Failure(reason.to_string()),
+                });
+        }
+
+        for (location, link) in self.ignored_links() {
+            suites
+                .entry(location.path().display().to_string())
+                .or_default()
+                .push(TestCase {
+                    name: link.href().to_string(),
+                    outcome: CaseOutcome::Skipped,
+                });
+        }
+
+        render_junit(&suites)
+    }
+}
+
+struct TestCase {
+    name: String,
+    outcome: CaseOutcome,
+}
+
+enum CaseOutcome {
+    Pass,
+    Failure(String),
+    Skipped,
+}
+
+fn render_junit(suites: &BTreeMap<String, Vec<TestCase>>) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+
+    for (path, cases) in suites {
+        let failures = cases
+            .iter()
+            .filter(|case| matches!(case.outcome, CaseOutcome::Failure(_)))
+            .count();
+
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            escape(path),
+            cases.len(),
+            failures
+        );
+
+        for case in cases {
+            match &case.outcome {
+                CaseOutcome::Pass => {
+                    let _ = writeln!(xml, "    <testcase name=\"{}\"/>", escape(&case.name));
+                }
+                CaseOutcome::Failure(reason) => {
+                    let _ = writeln!(xml, "    <testcase name=\"{}\">", escape(&case.name));
+                    let _ = writeln!(
+                        xml,
+                        "      <failure message=\"{}\"/>",
+                        escape(reason)
+                    );
+                    xml.push_str("    </testcase>\n");
+                }
+                CaseOutcome::Skipped => {
+                    let _ = writeln!(xml, "    <testcase name=\"{}\">", escape(&case.name));
+                    xml.push_str("      <skipped/>\n    </testcase>\n");
+                }
+            }
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Escape the handful of characters that aren't allowed in an XML
+/// attribute value.
+fn escape(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Link, Location};
+    use std::io;
+
+    use super::super::ValidationResult;
+
+    #[test]
+    fn to_json_schema_for_an_empty_outcome() {
+        let report = Outcome::default().to_json();
+
+        assert_eq!(report["total"], 0);
+        assert_eq!(report["valid"], 0);
+        assert_eq!(report["invalid"], 0);
+        assert_eq!(report["ignored"], 0);
+        assert_eq!(report["failures"], json!([]));
+    }
+
+    fn outcome_with_a_valid_and_an_invalid_link() -> Outcome {
+        Outcome::default()
+            .with_result(
+                Location::new("docs/guide.md", 4, 2),
+                Link::new("./installed.md"),
+                ValidationResult::Valid,
+            )
+            .with_result(
+                Location::new("docs/guide.md", 10, 5),
+                Link::new("./missing.md"),
+                ValidationResult::Invalid {
+                    reason: Box::new(io::Error::new(io::ErrorKind::NotFound, "no such file")),
+                },
+            )
+    }
+
+    #[test]
+    fn to_json_reports_a_real_invalid_link() {
+        let report = outcome_with_a_valid_and_an_invalid_link().to_json();
+
+        assert_eq!(report["total"], 2);
+        assert_eq!(report["valid"], 1);
+        assert_eq!(report["invalid"], 1);
+        assert_eq!(report["failures"][0]["href"], "./missing.md");
+        assert_eq!(report["failures"][0]["path"], "docs/guide.md");
+        assert_eq!(report["failures"][0]["line"], 10);
+        assert_eq!(report["failures"][0]["column"], 5);
+        assert_eq!(report["failures"][0]["reason"], "no such file");
+    }
+
+    #[test]
+    fn to_junit_groups_a_real_outcome_by_file() {
+        let xml = outcome_with_a_valid_and_an_invalid_link().to_junit();
+
+        assert!(xml.contains(r#"<testsuite name="docs/guide.md" tests="2" failures="1">"#));
+        assert!(xml.contains(r#"<testcase name="./installed.md"/>"#));
+        assert!(xml.contains(r#"<testcase name="./missing.md">"#));
+        assert!(xml.contains(r#"<failure message="no such file"/>"#));
+    }
+
+    #[test]
+    fn escape_handles_xml_special_characters() {
+        assert_eq!(escape(r#"<a href="x">"#), "&lt;a href=&quot;x&quot;&gt;");
+        assert_eq!(escape("Tom & Jerry"), "Tom &amp; Jerry");
+        assert_eq!(escape("plain"), "plain");
+    }
+
+    #[test]
+    fn render_junit_groups_testcases_by_file() {
+        let mut suites: BTreeMap<String, Vec<TestCase>> = BTreeMap::new();
+        suites.insert(
+            "docs/guide.md".to_string(),
+            vec![
+                TestCase {
+                    name: "./other.md".to_string(),
+                    outcome: CaseOutcome::Pass,
+                },
+                TestCase {
+                    name: "./missing.md".to_string(),
+                    outcome: CaseOutcome::Failure("no such file".to_string()),
+                },
+                TestCase {
+                    name: "ftp://example.com".to_string(),
+                    outcome: CaseOutcome::Skipped,
+                },
+            ],
+        );
+
+        let xml = render_junit(&suites);
+
+        assert!(xml.contains(r#"<testsuite name="docs/guide.md" tests="3" failures="1">"#));
+        assert!(xml.contains(r#"<testcase name="./other.md"/>"#));
+        assert!(xml.contains(r#"<failure message="no such file"/>"#));
+        assert!(xml.contains("<skipped/>"));
+    }
+
+    #[test]
+    fn render_junit_escapes_failure_messages() {
+        let mut suites: BTreeMap<String, Vec<TestCase>> = BTreeMap::new();
+        suites.insert(
+            "docs/guide.md".to_string(),
+            vec![TestCase {
+                name: "./missing.md".to_string(),
+                outcome: CaseOutcome::Failure(r#"404 for <a href="x">"#.to_string()),
+            }],
+        );
+
+        let xml = render_junit(&suites);
+
+        assert!(xml.contains("&lt;a href=&quot;x&quot;&gt;"));
+    }
+}
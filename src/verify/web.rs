@@ -0,0 +1,347 @@
+//! Verifying links which point at a resource on the web.
+
+use super::{fragment, ValidationResult, Verifier};
+use crate::{Cache, Link};
+use rand::Rng;
+use reqwest::{
+    blocking::{Client, Response},
+    header::{CONTENT_TYPE, RETRY_AFTER},
+    StatusCode,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::Duration,
+    time::Instant,
+};
+
+/// How many seconds' worth of requests a host is allowed to burst through
+/// before being throttled down to its steady-state rate.
+const BURST_SECONDS: f64 = 1.5;
+
+const DEFAULT_REQUESTS_PER_SECOND: f64 = 2.0;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// A [`Verifier`] for checking links which point at a URL on the web.
+///
+/// Requests to any single host are throttled to a configurable
+/// requests-per-second budget, and transient failures (timeouts,
+/// connection resets, `429` and any `5xx` responses) are retried with exponential
+/// backoff.
+#[derive(Debug)]
+pub struct Web {
+    client: Client,
+    requests_per_second: f64,
+    max_retries: u32,
+    base_delay: Duration,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl Web {
+    /// Create a new [`Web`] verifier using sensible default politeness
+    /// settings.
+    pub fn new() -> Self {
+        Web::with_config(
+            DEFAULT_REQUESTS_PER_SECOND,
+            DEFAULT_MAX_RETRIES,
+            DEFAULT_BASE_DELAY,
+        )
+    }
+
+    /// Create a [`Web`] verifier with explicit politeness settings.
+    ///
+    /// - `requests_per_second` caps how often we'll hit any single host.
+    /// - `max_retries` is how many times a transient failure is retried
+    ///   before giving up.
+    /// - `base_delay` is the starting point for the exponential backoff
+    ///   between retries; it doubles on every attempt and is jittered to
+    ///   avoid a thundering herd.
+    pub fn with_config(
+        requests_per_second: f64,
+        max_retries: u32,
+        base_delay: Duration,
+    ) -> Self {
+        Web {
+            client: Client::new(),
+            requests_per_second,
+            max_retries,
+            base_delay,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn acquire_token(&self, host: &str) {
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                buckets
+                    .entry(host.to_string())
+                    .or_insert_with(|| TokenBucket::new(self.requests_per_second))
+                    .try_acquire()
+            };
+
+            match wait {
+                Some(duration) => thread::sleep(duration),
+                None => return,
+            }
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self.base_delay * 2u32.saturating_pow(attempt);
+        let jitter_ms = rand::thread_rng().gen_range(0..=(exponential.as_millis() as u64 / 4) + 1);
+        exponential + Duration::from_millis(jitter_ms)
+    }
+
+    /// Issue a `GET` request to `url`, retrying on transient failures and
+    /// respecting the per-host request budget.
+    fn get_with_retries(
+        &self,
+        host: &str,
+        url: &str,
+    ) -> Result<Response, Box<dyn Error + Send + Sync>> {
+        let mut attempt = 0;
+
+        loop {
+            self.acquire_token(host);
+
+            match self.client.get(url).send() {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let status = response.status();
+
+                    if attempt >= self.max_retries || !is_transient(status) {
+                        return Err(response.error_for_status().unwrap_err().into());
+                    }
+
+                    thread::sleep(retry_after(&response).unwrap_or_else(|| self.backoff(attempt)));
+                }
+                Err(e) => {
+                    if attempt >= self.max_retries || !(e.is_timeout() || e.is_connect()) {
+                        return Err(Box::new(e));
+                    }
+
+                    thread::sleep(self.backoff(attempt));
+                }
+            }
+
+            attempt += 1;
+        }
+    }
+}
+
+impl Default for Web {
+    fn default() -> Self { Web::new() }
+}
+
+impl Verifier for Web {
+    fn verify(&self, link: &Link, _cache: &dyn Cache) -> ValidationResult {
+        let (raw_url, fragment) = split_fragment(link.href());
+
+        if !is_http(raw_url) {
+            return ValidationResult::Unsupported;
+        }
+
+        let url = match reqwest::Url::parse(raw_url) {
+            Ok(url) => url,
+            Err(e) => return ValidationResult::Invalid { reason: Box::new(e) },
+        };
+        let host = url.host_str().unwrap_or_default().to_string();
+
+        let response = match self.get_with_retries(&host, url.as_str()) {
+            Ok(response) => response,
+            Err(reason) => return ValidationResult::Invalid { reason },
+        };
+
+        if let Some(fragment) = fragment {
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+
+            let body = match response.text() {
+                Ok(body) => body,
+                Err(e) => return ValidationResult::Invalid { reason: Box::new(e) },
+            };
+
+            let path = document_path(&url, content_type.as_deref());
+
+            if !fragment::document_has_anchor(&path, &body, fragment) {
+                return ValidationResult::Invalid {
+                    reason: fragment::missing_anchor_error(fragment),
+                };
+            }
+        }
+
+        ValidationResult::Valid
+    }
+}
+
+/// A per-host token bucket, refilling at a fixed rate with a small burst
+/// allowance.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    capacity: f64,
+    refill_per_second: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(refill_per_second: f64) -> Self {
+        let capacity = (refill_per_second * BURST_SECONDS).max(1.0);
+
+        TokenBucket {
+            tokens: capacity,
+            capacity,
+            refill_per_second,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Try to take a single token. Returns `None` if one was available, or
+    /// `Some(duration)` the caller should sleep for before trying again.
+    fn try_acquire(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let deficit = 1.0 - self.tokens;
+            Some(Duration::from_secs_f64(deficit / self.refill_per_second))
+        }
+    }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    ) || status.is_server_error()
+}
+
+/// Parse a `Retry-After` header expressed in seconds, if present.
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// Work out what kind of document `url` points at, so [`fragment`] knows
+/// whether to look for a slugified Markdown heading or an HTML `id`/`name`
+/// attribute.
+///
+/// The URL path's extension is the primary signal (this is what
+/// `file.rs` uses too); when it's missing or unrecognised we fall back to
+/// the response's `Content-Type` header, which is common for URLs like
+/// `raw.githubusercontent.com/.../README.md` served without an extension.
+fn document_path(url: &reqwest::Url, content_type: Option<&str>) -> PathBuf {
+    let mut path = PathBuf::from(url.path());
+    let recognised = matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("md") | Some("markdown") | Some("html") | Some("htm")
+    );
+
+    if !recognised {
+        if let Some(content_type) = content_type {
+            if content_type.contains("markdown") {
+                path.set_extension("md");
+            } else if content_type.contains("html") {
+                path.set_extension("html");
+            }
+        }
+    }
+
+    path
+}
+
+fn is_http(url: &str) -> bool { url.starts_with("http://") || url.starts_with("https://") }
+
+fn split_fragment(href: &str) -> (&str, Option<&str>) {
+    match href.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (href, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_allows_bursting_up_to_capacity() {
+        let mut bucket = TokenBucket::new(2.0);
+        let capacity = (2.0 * BURST_SECONDS).max(1.0) as usize;
+
+        for _ in 0..capacity {
+            assert!(bucket.try_acquire().is_none(), "burst capacity exhausted early");
+        }
+
+        // The bucket should now be empty and make the caller wait.
+        assert!(bucket.try_acquire().is_some());
+    }
+
+    #[test]
+    fn token_bucket_wait_is_proportional_to_refill_rate() {
+        let mut bucket = TokenBucket::new(1.0);
+        bucket.tokens = 0.0;
+
+        let wait = bucket.try_acquire().expect("bucket should be empty");
+        // At 1 token/second, needing a full token back means waiting ~1s.
+        assert!(wait.as_secs_f64() > 0.9 && wait.as_secs_f64() <= 1.0);
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_with_jitter_bounded() {
+        let web = Web::with_config(1.0, 3, Duration::from_millis(100));
+
+        for attempt in 0..4 {
+            let delay = web.backoff(attempt);
+            let minimum = Duration::from_millis(100) * 2u32.pow(attempt);
+            let maximum = minimum + minimum / 4 + Duration::from_millis(1);
+
+            assert!(delay >= minimum, "attempt {attempt}: {delay:?} < {minimum:?}");
+            assert!(delay <= maximum, "attempt {attempt}: {delay:?} > {maximum:?}");
+        }
+    }
+
+    #[test]
+    fn is_transient_covers_retryable_statuses() {
+        assert!(is_transient(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_transient(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_transient(StatusCode::NOT_FOUND));
+        assert!(!is_transient(StatusCode::OK));
+    }
+
+    #[test]
+    fn document_path_prefers_url_extension_over_content_type() {
+        let url = reqwest::Url::parse("https://example.com/README.md").unwrap();
+        assert_eq!(
+            document_path(&url, Some("text/plain")),
+            PathBuf::from("/README.md")
+        );
+    }
+
+    #[test]
+    fn document_path_falls_back_to_content_type_without_extension() {
+        let url = reqwest::Url::parse("https://raw.githubusercontent.com/org/repo/HEAD/README")
+            .unwrap();
+        assert_eq!(
+            document_path(&url, Some("text/markdown; charset=utf-8")),
+            PathBuf::from("/org/repo/HEAD/README.md")
+        );
+    }
+}